@@ -0,0 +1,126 @@
+use std::cell::RefCell;
+
+use crate::color::Color;
+use crate::render::Backend;
+
+/// An SGR attribute currently "held open" by the backend.
+#[derive(Clone)]
+enum Attr {
+    Bold,
+    Italic,
+    Dim,
+    Color(Color),
+}
+
+impl Attr {
+    fn escape(&self) -> String {
+        match self {
+            Attr::Bold => "\x1b[1m".to_string(),
+            Attr::Italic => "\x1b[3m".to_string(),
+            Attr::Dim => "\x1b[8m".to_string(),
+            Attr::Color(color) => color.to_ansi_fg(),
+        }
+    }
+}
+
+/// Renders to ANSI SGR escape codes for a styled terminal preview.
+///
+/// `Text` opens attributes outermost-first and closes them innermost-first, so this backend sees
+/// properly nested push/pop calls. Closing an attribute never blindly resets
+/// (`\x1b[0m` would clear every enclosing attribute too); instead it pops its
+/// own entry off the stack and re-emits whatever is still active underneath.
+#[derive(Default)]
+pub struct Ansi {
+    stack: RefCell<Vec<Attr>>,
+}
+
+impl Ansi {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn open(&self, attr: Attr) -> String {
+        let escape = attr.escape();
+        self.stack.borrow_mut().push(attr);
+        escape
+    }
+
+    fn close(&self) -> String {
+        self.stack.borrow_mut().pop();
+
+        let stack = self.stack.borrow();
+        let mut out = "\x1b[0m".to_string();
+        for attr in stack.iter() {
+            out += &attr.escape();
+        }
+        out
+    }
+}
+
+impl Backend for Ansi {
+    fn open_bold(&self) -> String {
+        self.open(Attr::Bold)
+    }
+
+    fn close_bold(&self) -> String {
+        self.close()
+    }
+
+    fn open_italic(&self) -> String {
+        self.open(Attr::Italic)
+    }
+
+    fn close_italic(&self) -> String {
+        self.close()
+    }
+
+    fn color(&self, open: bool, value: &Color) -> String {
+        if open {
+            self.open(Attr::Color(value.clone()))
+        } else {
+            self.close()
+        }
+    }
+
+    fn size(&self, _open: bool, _value: usize) -> String {
+        // No terminal notion of font size; drop it silently.
+        String::new()
+    }
+
+    fn image(&self, url: &str) -> String {
+        format!("[img: {url}]")
+    }
+
+    fn spoiler(&self, body: String) -> String {
+        let open = self.open(Attr::Dim);
+        let close = self.close();
+        format!("{open}{body}{close}")
+    }
+
+    fn join_list(&self, items: Vec<String>, separator: &str) -> String {
+        items.join(separator)
+    }
+
+    fn supports_box_drawing(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closing_an_inner_attribute_re_emits_the_enclosing_one() {
+        let ansi = Ansi::new();
+        let red = Color::Rgb(255, 0, 0);
+
+        assert_eq!(ansi.open_bold(), "\x1b[1m");
+        assert_eq!(ansi.color(true, &red), red.to_ansi_fg());
+
+        // Closing the innermost attribute must not emit a bare reset; bold
+        // is still open underneath, so it has to come back.
+        assert_eq!(ansi.color(false, &red), format!("\x1b[0m{}", Attr::Bold.escape()));
+        assert_eq!(ansi.close_bold(), "\x1b[0m");
+    }
+}
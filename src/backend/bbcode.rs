@@ -0,0 +1,55 @@
+use crate::color::Color;
+use crate::render::Backend;
+
+/// The original output format: forum-flavoured BBCode.
+pub struct Bbcode;
+
+impl Backend for Bbcode {
+    fn open_bold(&self) -> String {
+        "[b]".to_string()
+    }
+
+    fn close_bold(&self) -> String {
+        "[/b]".to_string()
+    }
+
+    fn open_italic(&self) -> String {
+        "[i]".to_string()
+    }
+
+    fn close_italic(&self) -> String {
+        "[/i]".to_string()
+    }
+
+    fn color(&self, open: bool, value: &Color) -> String {
+        if open {
+            format!("[color={}]", value.to_bbcode_value())
+        } else {
+            "[/color]".to_string()
+        }
+    }
+
+    fn size(&self, open: bool, value: usize) -> String {
+        if open {
+            format!("[size={value}]")
+        } else {
+            "[/size]".to_string()
+        }
+    }
+
+    fn image(&self, url: &str) -> String {
+        format!("[img]{url}[/img]")
+    }
+
+    fn spoiler(&self, body: String) -> String {
+        format!("[spoiler]{body}[/spoiler]")
+    }
+
+    fn join_list(&self, items: Vec<String>, separator: &str) -> String {
+        items.join(separator)
+    }
+
+    fn quote(&self, body: String) -> String {
+        format!("[quote]{body}[/quote]")
+    }
+}
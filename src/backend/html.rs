@@ -0,0 +1,55 @@
+use crate::color::Color;
+use crate::render::Backend;
+
+/// Renders to HTML, suitable for embedding in a web page.
+pub struct Html;
+
+impl Backend for Html {
+    fn open_bold(&self) -> String {
+        "<b>".to_string()
+    }
+
+    fn close_bold(&self) -> String {
+        "</b>".to_string()
+    }
+
+    fn open_italic(&self) -> String {
+        "<i>".to_string()
+    }
+
+    fn close_italic(&self) -> String {
+        "</i>".to_string()
+    }
+
+    fn color(&self, open: bool, value: &Color) -> String {
+        if open {
+            format!("<span style=\"color:{}\">", value.to_css_value())
+        } else {
+            "</span>".to_string()
+        }
+    }
+
+    fn size(&self, open: bool, value: usize) -> String {
+        if open {
+            format!("<span style=\"font-size:{value}px\">")
+        } else {
+            "</span>".to_string()
+        }
+    }
+
+    fn image(&self, url: &str) -> String {
+        format!("<img src=\"{url}\">")
+    }
+
+    fn spoiler(&self, body: String) -> String {
+        format!("<details><summary>Spoiler</summary>{body}</details>")
+    }
+
+    fn join_list(&self, items: Vec<String>, separator: &str) -> String {
+        items.join(separator)
+    }
+
+    fn quote(&self, body: String) -> String {
+        format!("<blockquote>{body}</blockquote>")
+    }
+}
@@ -0,0 +1,13 @@
+mod bbcode;
+mod html;
+mod plain;
+
+#[cfg(feature = "ansi")]
+mod ansi;
+
+pub use bbcode::Bbcode;
+pub use html::Html;
+pub use plain::Plain;
+
+#[cfg(feature = "ansi")]
+pub use ansi::Ansi;
@@ -0,0 +1,48 @@
+use crate::color::Color;
+use crate::render::Backend;
+
+/// Drops all styling and markup, leaving bare concatenated text; layout
+/// `Block`s are still drawn as real bordered boxes.
+pub struct Plain;
+
+impl Backend for Plain {
+    fn open_bold(&self) -> String {
+        String::new()
+    }
+
+    fn close_bold(&self) -> String {
+        String::new()
+    }
+
+    fn open_italic(&self) -> String {
+        String::new()
+    }
+
+    fn close_italic(&self) -> String {
+        String::new()
+    }
+
+    fn color(&self, _open: bool, _value: &Color) -> String {
+        String::new()
+    }
+
+    fn size(&self, _open: bool, _value: usize) -> String {
+        String::new()
+    }
+
+    fn image(&self, url: &str) -> String {
+        url.to_string()
+    }
+
+    fn spoiler(&self, body: String) -> String {
+        body
+    }
+
+    fn join_list(&self, items: Vec<String>, separator: &str) -> String {
+        items.join(separator)
+    }
+
+    fn supports_box_drawing(&self) -> bool {
+        true
+    }
+}
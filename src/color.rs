@@ -0,0 +1,154 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// A color that can be rendered by any `Backend`, rather than a raw string
+/// that only happens to look right in BBCode.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Color {
+    Named(String),
+    Hex { r: u8, g: u8, b: u8 },
+    Rgb(u8, u8, u8),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ColorParseError(pub String);
+
+impl std::fmt::Display for ColorParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid color: {}", self.0)
+    }
+}
+
+impl std::error::Error for ColorParseError {}
+
+impl Color {
+    /// The RGB triple backing this color, resolving named colors against
+    /// the built-in table.
+    pub fn rgb(&self) -> (u8, u8, u8) {
+        match self {
+            Color::Hex { r, g, b } => (*r, *g, *b),
+            Color::Rgb(r, g, b) => (*r, *g, *b),
+            Color::Named(name) => named_color(name).unwrap_or((0, 0, 0)),
+        }
+    }
+
+    /// BBCode expects either the named color or a `#rrggbb` hex string.
+    pub fn to_bbcode_value(&self) -> String {
+        match self {
+            Color::Named(name) => name.clone(),
+            Color::Hex { r, g, b } => format!("#{r:02x}{g:02x}{b:02x}"),
+            Color::Rgb(r, g, b) => format!("#{r:02x}{g:02x}{b:02x}"),
+        }
+    }
+
+    /// CSS accepts the same named colors and hex notation BBCode does.
+    pub fn to_css_value(&self) -> String {
+        self.to_bbcode_value()
+    }
+
+    /// 24-bit SGR foreground escape sequence, for the ANSI backend.
+    pub fn to_ansi_fg(&self) -> String {
+        let (r, g, b) = self.rgb();
+        format!("\x1b[38;2;{r};{g};{b}m")
+    }
+}
+
+impl FromStr for Color {
+    type Err = ColorParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        if let Some(hex) = s.strip_prefix('#') {
+            return parse_hex(hex).ok_or_else(|| ColorParseError(s.to_string()));
+        }
+
+        if let Some(inner) = s
+            .strip_prefix("rgb(")
+            .and_then(|s| s.strip_suffix(')'))
+        {
+            let mut parts = inner.split(',').map(|p| p.trim().parse::<u8>());
+            let r = parts.next().and_then(Result::ok);
+            let g = parts.next().and_then(Result::ok);
+            let b = parts.next().and_then(Result::ok);
+            return match (r, g, b, parts.next()) {
+                (Some(r), Some(g), Some(b), None) => Ok(Color::Rgb(r, g, b)),
+                _ => Err(ColorParseError(s.to_string())),
+            };
+        }
+
+        if named_color(s).is_some() {
+            return Ok(Color::Named(s.to_string()));
+        }
+
+        Err(ColorParseError(s.to_string()))
+    }
+}
+
+fn parse_hex(hex: &str) -> Option<Color> {
+    let expand = |c: char| u8::from_str_radix(&format!("{c}{c}"), 16).ok();
+
+    match hex.len() {
+        6 => {
+            let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
+            let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
+            let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
+            Some(Color::Hex { r, g, b })
+        }
+        3 => {
+            let mut chars = hex.chars();
+            let r = expand(chars.next()?)?;
+            let g = expand(chars.next()?)?;
+            let b = expand(chars.next()?)?;
+            Some(Color::Hex { r, g, b })
+        }
+        _ => None,
+    }
+}
+
+fn named_color(name: &str) -> Option<(u8, u8, u8)> {
+    Some(match name.to_ascii_lowercase().as_str() {
+        "black" => (0, 0, 0),
+        "white" => (255, 255, 255),
+        "red" => (255, 0, 0),
+        "green" => (0, 128, 0),
+        "blue" => (0, 0, 255),
+        "yellow" => (255, 255, 0),
+        "cyan" => (0, 255, 255),
+        "magenta" => (255, 0, 255),
+        "orange" => (255, 165, 0),
+        "purple" => (128, 0, 128),
+        "gray" | "grey" => (128, 128, 128),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_short_and_long_hex() {
+        assert_eq!("#f00".parse(), Ok(Color::Hex { r: 255, g: 0, b: 0 }));
+        assert_eq!("#ff0000".parse(), Ok(Color::Hex { r: 255, g: 0, b: 0 }));
+    }
+
+    #[test]
+    fn parses_rgb_and_named() {
+        assert_eq!("rgb(1, 2, 3)".parse(), Ok(Color::Rgb(1, 2, 3)));
+        assert_eq!("red".parse(), Ok(Color::Named("red".to_string())));
+    }
+
+    #[test]
+    fn rejects_malformed_colors() {
+        let cases = ["#ff", "#gggggg", "rgb(1, 2)", "rgb(1, 2, 3, 4)", "rgb(1, 2, 300)", "not-a-color"];
+        for case in cases {
+            assert_eq!(
+                case.parse::<Color>(),
+                Err(ColorParseError(case.to_string())),
+                "expected {case:?} to fail to parse"
+            );
+        }
+    }
+}
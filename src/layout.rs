@@ -0,0 +1,521 @@
+//! Box layout: borders, padding, margins, and axis stacking for `Block`.
+//!
+//! Layout runs in two passes. `min_size` walks the tree bottom-up to find
+//! each node's smallest fitting size. `render_lines` walks it top-down,
+//! handing `Grow` children whatever space is left over after `Fixed`/`Auto`
+//! children have taken their minimum, then draws the result as a grid of
+//! lines (one `String` per row).
+
+use crate::loader::{Loader, RenderError};
+use crate::render::{Backend, Render};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Horizontal,
+    Vertical,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AxisSize {
+    /// A fixed number of columns (if the block's main axis) or rows.
+    Fixed(usize),
+    /// Exactly as big as its content requires.
+    Auto,
+    /// Takes an equal share of whatever space is left after `Fixed`/`Auto`
+    /// children have been sized.
+    Grow,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Border {
+    None,
+    Light,
+    Heavy,
+    Double,
+}
+
+struct BorderChars {
+    top_left: char,
+    top_right: char,
+    bottom_left: char,
+    bottom_right: char,
+    horizontal: char,
+    vertical: char,
+}
+
+impl Border {
+    fn chars(&self) -> Option<BorderChars> {
+        match self {
+            Border::None => None,
+            Border::Light => Some(BorderChars {
+                top_left: '┌',
+                top_right: '┐',
+                bottom_left: '└',
+                bottom_right: '┘',
+                horizontal: '─',
+                vertical: '│',
+            }),
+            Border::Heavy => Some(BorderChars {
+                top_left: '┏',
+                top_right: '┓',
+                bottom_left: '┗',
+                bottom_right: '┛',
+                horizontal: '━',
+                vertical: '┃',
+            }),
+            Border::Double => Some(BorderChars {
+                top_left: '╔',
+                top_right: '╗',
+                bottom_left: '╚',
+                bottom_right: '╝',
+                horizontal: '═',
+                vertical: '║',
+            }),
+        }
+    }
+
+    fn thickness(&self) -> usize {
+        if matches!(self, Border::None) {
+            0
+        } else {
+            1
+        }
+    }
+}
+
+/// Space around the four edges of a block, in character cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Sides {
+    pub top: usize,
+    pub right: usize,
+    pub bottom: usize,
+    pub left: usize,
+}
+
+impl Sides {
+    pub fn uniform(n: usize) -> Self {
+        Self {
+            top: n,
+            right: n,
+            bottom: n,
+            left: n,
+        }
+    }
+
+    fn horizontal(&self) -> usize {
+        self.left + self.right
+    }
+
+    fn vertical(&self) -> usize {
+        self.top + self.bottom
+    }
+}
+
+/// A leaf or a nested `Block`.
+pub enum Node {
+    Text(String),
+    Block(Block),
+}
+
+impl Node {
+    fn min_size(&self) -> (usize, usize) {
+        match self {
+            Node::Text(text) => {
+                let width = text.lines().map(|line| line.chars().count()).max().unwrap_or(0);
+                let height = text.lines().count().max(1);
+                (width, height)
+            }
+            Node::Block(block) => block.min_size(),
+        }
+    }
+
+    fn render_lines(&self, width: usize, height: usize) -> Vec<String> {
+        match self {
+            Node::Text(text) => {
+                let mut lines: Vec<String> = text
+                    .lines()
+                    .map(|line| pad_to(line, width))
+                    .collect();
+                if lines.is_empty() {
+                    lines.push(" ".repeat(width));
+                }
+                while lines.len() < height {
+                    lines.push(" ".repeat(width));
+                }
+                lines
+            }
+            Node::Block(block) => block.render_lines_sized(width, height),
+        }
+    }
+}
+
+/// A child of a `Block`, with its preferred size along the block's main axis.
+pub struct Child {
+    pub size: AxisSize,
+    pub content: Node,
+}
+
+impl Child {
+    pub fn new(size: AxisSize, content: Node) -> Self {
+        Self { size, content }
+    }
+}
+
+pub struct Block {
+    pub direction: Direction,
+    pub padding: Sides,
+    pub margin: Sides,
+    pub border: Border,
+    pub children: Vec<Child>,
+}
+
+impl Block {
+    pub fn new(direction: Direction) -> Self {
+        Self {
+            direction,
+            padding: Sides::default(),
+            margin: Sides::default(),
+            border: Border::None,
+            children: Vec::new(),
+        }
+    }
+
+    pub fn padding(mut self, padding: Sides) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    pub fn margin(mut self, margin: Sides) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    pub fn border(mut self, border: Border) -> Self {
+        self.border = border;
+        self
+    }
+
+    pub fn children(mut self, children: Vec<Child>) -> Self {
+        self.children = children;
+        self
+    }
+
+    /// `(content_main, content_cross)` minimum size of the child area, not
+    /// counting this block's own border/padding/margin.
+    fn content_min_size(&self) -> (usize, usize) {
+        let sizes: Vec<(usize, usize)> = self
+            .children
+            .iter()
+            .map(|child| child.content.min_size())
+            .collect();
+
+        match self.direction {
+            Direction::Horizontal => {
+                let main = self
+                    .children
+                    .iter()
+                    .zip(&sizes)
+                    .map(|(child, (w, _))| match child.size {
+                        AxisSize::Fixed(n) => n,
+                        AxisSize::Auto | AxisSize::Grow => *w,
+                    })
+                    .sum();
+                let cross = sizes.iter().map(|(_, h)| *h).max().unwrap_or(0);
+                (main, cross)
+            }
+            Direction::Vertical => {
+                let main = self
+                    .children
+                    .iter()
+                    .zip(&sizes)
+                    .map(|(child, (_, h))| match child.size {
+                        AxisSize::Fixed(n) => n,
+                        AxisSize::Auto | AxisSize::Grow => *h,
+                    })
+                    .sum();
+                let cross = sizes.iter().map(|(w, _)| *w).max().unwrap_or(0);
+                (main, cross)
+            }
+        }
+    }
+
+    /// Total size this block occupies, including its own border, padding and
+    /// margin, as `(width, height)`.
+    pub fn min_size(&self) -> (usize, usize) {
+        let (main, cross) = self.content_min_size();
+        let border = self.border.thickness() * 2;
+
+        match self.direction {
+            Direction::Horizontal => (
+                main + self.padding.horizontal() + border + self.margin.horizontal(),
+                cross + self.padding.vertical() + border + self.margin.vertical(),
+            ),
+            Direction::Vertical => (
+                cross + self.padding.horizontal() + border + self.margin.horizontal(),
+                main + self.padding.vertical() + border + self.margin.vertical(),
+            ),
+        }
+    }
+
+    /// Render at this block's own minimum size.
+    pub fn render_lines(&self) -> Vec<String> {
+        let (width, height) = self.min_size();
+        self.render_lines_sized(width, height)
+    }
+
+    /// Structural fallback for backends that can't draw real boxes: nest
+    /// each bordered child block in `Backend::quote` instead of drawing a
+    /// border; child blocks without a border are inlined unquoted.
+    fn render_fallback<B: Backend>(&self, backend: &B) -> String {
+        let parts: Vec<String> = self
+            .children
+            .iter()
+            .map(|child| match &child.content {
+                Node::Text(text) => text.clone(),
+                Node::Block(block) => {
+                    let body = block.render_fallback(backend);
+                    if block.border == Border::None {
+                        body
+                    } else {
+                        backend.quote(body)
+                    }
+                }
+            })
+            .collect();
+
+        let separator = match self.direction {
+            Direction::Horizontal => " ",
+            Direction::Vertical => "\n",
+        };
+
+        backend.join_list(parts, separator)
+    }
+
+    /// Render into an outer box of exactly `width` x `height`, growing
+    /// `Grow` children to fill any space left over after `Fixed`/`Auto`
+    /// children take their minimum.
+    fn render_lines_sized(&self, width: usize, height: usize) -> Vec<String> {
+        let border = self.border.thickness();
+        let inner_width = width.saturating_sub(self.margin.horizontal() + border * 2);
+        let inner_height = height.saturating_sub(self.margin.vertical() + border * 2);
+
+        let content_width = inner_width.saturating_sub(self.padding.horizontal());
+        let content_height = inner_height.saturating_sub(self.padding.vertical());
+
+        let content_lines = match self.direction {
+            Direction::Horizontal => self.render_main_axis(content_width, content_height, true),
+            Direction::Vertical => self.render_main_axis(content_height, content_width, false),
+        };
+
+        let padded = pad_block(content_lines, content_width, content_height, &self.padding);
+        let bordered = draw_border(padded, inner_width, inner_height, self.border);
+        apply_margin(bordered, &self.margin)
+    }
+
+    /// Lays children out along whichever axis is "main" for this block.
+    /// `main_extent`/`cross_extent` are already the content box size with
+    /// `main` meaning the stacking axis regardless of `horizontal`.
+    fn render_main_axis(&self, main_extent: usize, cross_extent: usize, horizontal: bool) -> Vec<String> {
+        let mins: Vec<usize> = self
+            .children
+            .iter()
+            .map(|child| {
+                let (w, h) = child.content.min_size();
+                if horizontal {
+                    w
+                } else {
+                    h
+                }
+            })
+            .collect();
+
+        let fixed_or_auto_total: usize = self
+            .children
+            .iter()
+            .zip(&mins)
+            .map(|(child, min)| match child.size {
+                AxisSize::Fixed(n) => n,
+                AxisSize::Auto | AxisSize::Grow => *min,
+            })
+            .sum();
+
+        let grow_count = self
+            .children
+            .iter()
+            .filter(|child| matches!(child.size, AxisSize::Grow))
+            .count();
+
+        let surplus = main_extent.saturating_sub(fixed_or_auto_total);
+        let grow_share = surplus.checked_div(grow_count).unwrap_or(0);
+        let mut grow_remainder = if grow_count > 0 { surplus % grow_count } else { 0 };
+
+        let mut rendered_children: Vec<Vec<String>> = Vec::with_capacity(self.children.len());
+
+        for (child, min) in self.children.iter().zip(&mins) {
+            let main = match child.size {
+                AxisSize::Fixed(n) => n,
+                AxisSize::Auto => *min,
+                AxisSize::Grow => {
+                    let mut size = *min + grow_share;
+                    if grow_remainder > 0 {
+                        size += 1;
+                        grow_remainder -= 1;
+                    }
+                    size
+                }
+            };
+
+            let (w, h) = if horizontal {
+                (main, cross_extent)
+            } else {
+                (cross_extent, main)
+            };
+
+            rendered_children.push(child.content.render_lines(w, h));
+        }
+
+        if horizontal {
+            join_horizontal(rendered_children, cross_extent)
+        } else {
+            rendered_children.into_iter().flatten().collect()
+        }
+    }
+}
+
+fn join_horizontal(blocks: Vec<Vec<String>>, height: usize) -> Vec<String> {
+    let mut out = vec![String::new(); height];
+    for block in blocks {
+        for (row, line) in out.iter_mut().zip(block) {
+            row.push_str(&line);
+        }
+    }
+    out
+}
+
+fn pad_to(line: &str, width: usize) -> String {
+    let len = line.chars().count();
+    if len >= width {
+        line.to_string()
+    } else {
+        format!("{line}{}", " ".repeat(width - len))
+    }
+}
+
+fn pad_block(lines: Vec<String>, width: usize, height: usize, padding: &Sides) -> Vec<String> {
+    let mut out = Vec::with_capacity(height + padding.vertical());
+
+    for _ in 0..padding.top {
+        out.push(" ".repeat(width + padding.horizontal()));
+    }
+
+    for line in lines {
+        out.push(format!(
+            "{}{}{}",
+            " ".repeat(padding.left),
+            pad_to(&line, width),
+            " ".repeat(padding.right)
+        ));
+    }
+
+    while out.len() < padding.top + height {
+        out.push(" ".repeat(width + padding.horizontal()));
+    }
+
+    for _ in 0..padding.bottom {
+        out.push(" ".repeat(width + padding.horizontal()));
+    }
+
+    out
+}
+
+fn draw_border(lines: Vec<String>, inner_width: usize, inner_height: usize, border: Border) -> Vec<String> {
+    let Some(chars) = border.chars() else {
+        return lines;
+    };
+
+    let mut out = Vec::with_capacity(inner_height + 2);
+
+    out.push(format!(
+        "{}{}{}",
+        chars.top_left,
+        chars.horizontal.to_string().repeat(inner_width),
+        chars.top_right
+    ));
+
+    for line in lines {
+        out.push(format!("{}{}{}", chars.vertical, pad_to(&line, inner_width), chars.vertical));
+    }
+
+    out.push(format!(
+        "{}{}{}",
+        chars.bottom_left,
+        chars.horizontal.to_string().repeat(inner_width),
+        chars.bottom_right
+    ));
+
+    out
+}
+
+fn apply_margin(lines: Vec<String>, margin: &Sides) -> Vec<String> {
+    if margin == &Sides::default() {
+        return lines;
+    }
+
+    let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0) + margin.horizontal();
+    let mut out = Vec::with_capacity(lines.len() + margin.vertical());
+
+    for _ in 0..margin.top {
+        out.push(" ".repeat(width));
+    }
+
+    for line in lines {
+        out.push(format!("{}{}{}", " ".repeat(margin.left), line, " ".repeat(margin.right)));
+    }
+
+    for _ in 0..margin.bottom {
+        out.push(" ".repeat(width));
+    }
+
+    out
+}
+
+impl<B: Backend> Render<B> for Block {
+    fn render(self, backend: &B, _loader: &Loader) -> Result<String, RenderError> {
+        if backend.supports_box_drawing() {
+            Ok(self.render_lines().join("\n"))
+        } else {
+            // `render_fallback` only quotes *nested* child blocks (it has no
+            // way to quote itself from the inside); a bordered block still
+            // needs its border to show up somehow, so quote the whole
+            // rendered body here too, unless there's no border to stand in for.
+            let border = self.border;
+            let body = self.render_fallback(backend);
+            if border == Border::None {
+                Ok(body)
+            } else {
+                Ok(backend.quote(body))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grow_children_split_leftover_space_with_remainder_going_first() {
+        let block = Block::new(Direction::Horizontal).children(vec![
+            Child::new(AxisSize::Grow, Node::Text("a".to_string())),
+            Child::new(AxisSize::Grow, Node::Text("b".to_string())),
+        ]);
+
+        // main_extent 11 minus the two 1-wide minimums leaves a surplus of 9,
+        // which doesn't split evenly between 2 grow children: the first gets
+        // the extra column (1 + 4 + 1 = 6), the second gets the rest (1 + 4 = 5).
+        let lines = block.render_main_axis(11, 1, true);
+        let line = &lines[0];
+        assert_eq!(line.len(), 11);
+        assert_eq!(line.find('b'), Some(6));
+    }
+}
@@ -0,0 +1,160 @@
+use std::any::TypeId;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::render::{Backend, Render};
+
+/// How an `Include` failed to resolve, parse, or render.
+#[derive(Debug)]
+pub enum RenderError {
+    Io { path: PathBuf, source: std::io::Error },
+    Parse { path: PathBuf, message: String },
+    CycleDetected(Vec<PathBuf>),
+    MaxDepthExceeded,
+}
+
+impl std::fmt::Display for RenderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenderError::Io { path, source } => {
+                write!(f, "failed to read {}: {source}", path.display())
+            }
+            RenderError::Parse { path, message } => {
+                write!(f, "failed to parse {}: {message}", path.display())
+            }
+            RenderError::CycleDetected(chain) => {
+                let chain = chain
+                    .iter()
+                    .map(|p| p.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(" -> ");
+                write!(f, "include cycle detected: {chain}")
+            }
+            RenderError::MaxDepthExceeded => write!(f, "include nesting exceeded the maximum depth"),
+        }
+    }
+}
+
+impl std::error::Error for RenderError {}
+
+/// Resolves, parses, and renders `Include` targets.
+///
+/// URIs are resolved relative to the directory of the document that
+/// contains the `Include`, not the process's current directory. Rendered
+/// results are cached by `(canonical path, Backend type)` so a file included
+/// from several places is only read and parsed once *per backend* — the same
+/// `Loader` can safely render a document through BBCode and then HTML
+/// without the second call seeing the first backend's cached output. The
+/// chain of documents currently being rendered is tracked so a
+/// self-referential include is reported as a `CycleDetected` error instead
+/// of recursing forever.
+pub struct Loader {
+    cache: RefCell<HashMap<(PathBuf, TypeId), String>>,
+    stack: RefCell<Vec<PathBuf>>,
+    max_depth: usize,
+}
+
+impl Default for Loader {
+    fn default() -> Self {
+        Self {
+            cache: RefCell::new(HashMap::new()),
+            stack: RefCell::new(Vec::new()),
+            max_depth: 32,
+        }
+    }
+}
+
+impl Loader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn current_dir(&self) -> PathBuf {
+        self.stack
+            .borrow()
+            .last()
+            .and_then(|path| path.parent())
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| PathBuf::from("."))
+    }
+
+    /// Resolve `uri` against the including document's directory, then
+    /// parse and render it as `T`.
+    pub fn load<T, B>(&self, uri: &str, backend: &B) -> Result<String, RenderError>
+    where
+        T: for<'de> Deserialize<'de> + Render<B>,
+        B: Backend,
+    {
+        let resolved = self.current_dir().join(uri);
+        let canonical = resolved
+            .canonicalize()
+            .map_err(|source| RenderError::Io { path: resolved.clone(), source })?;
+        let cache_key = (canonical.clone(), TypeId::of::<B>());
+
+        if let Some(cached) = self.cache.borrow().get(&cache_key) {
+            return Ok(cached.clone());
+        }
+
+        if self.stack.borrow().contains(&canonical) {
+            let mut chain = self.stack.borrow().clone();
+            chain.push(canonical);
+            return Err(RenderError::CycleDetected(chain));
+        }
+
+        if self.stack.borrow().len() >= self.max_depth {
+            return Err(RenderError::MaxDepthExceeded);
+        }
+
+        let contents = std::fs::read_to_string(&canonical)
+            .map_err(|source| RenderError::Io { path: canonical.clone(), source })?;
+        let value: T = ron::from_str(&contents).map_err(|error| RenderError::Parse {
+            path: canonical.clone(),
+            message: error.to_string(),
+        })?;
+
+        self.stack.borrow_mut().push(canonical.clone());
+        let rendered = value.render(backend, self);
+        self.stack.borrow_mut().pop();
+        let rendered = rendered?;
+
+        self.cache.borrow_mut().insert(cache_key, rendered.clone());
+        Ok(rendered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::Plain;
+
+    /// A document whose `Render` impl includes itself, for exercising
+    /// `CycleDetected` without needing a second file on disk.
+    #[derive(Deserialize)]
+    struct SelfInclude;
+
+    impl<B: Backend> Render<B> for SelfInclude {
+        fn render(self, backend: &B, loader: &Loader) -> Result<String, RenderError> {
+            loader.load::<SelfInclude, B>("self.ron", backend)
+        }
+    }
+
+    #[test]
+    fn detects_include_cycles() {
+        let dir = std::env::temp_dir().join(format!("cml_loader_cycle_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("self.ron");
+        std::fs::write(&path, "()").unwrap();
+
+        let loader = Loader::new();
+        let error = loader
+            .load::<SelfInclude, Plain>(path.to_str().unwrap(), &Plain)
+            .unwrap_err();
+
+        assert!(matches!(error, RenderError::CycleDetected(_)), "expected a cycle, got {error:?}");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
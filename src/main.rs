@@ -2,26 +2,30 @@ use std::marker::PhantomData;
 
 use serde::{Deserialize, Serialize};
 
-trait ToBBCode {
-    fn to_bbcode(self) -> String;
-}
-
-impl ToBBCode for String {
-    fn to_bbcode(self) -> String {
-        self
-    }
-}
+mod backend;
+mod color;
+mod layout;
+mod loader;
+mod render;
+
+use backend::{Bbcode, Html, Plain};
+#[cfg(feature = "ansi")]
+use backend::Ansi;
+use color::Color;
+use layout::{AxisSize, Block, Border, Child, Direction, Node, Sides};
+use loader::{Loader, RenderError};
+use render::{Backend, Render};
 
 #[derive(Debug, Default, Deserialize, Serialize)]
 pub struct TextStyle {
-    color: Option<String>,
+    color: Option<Color>,
     size: Option<usize>,
     bold: bool,
     italic: bool,
 }
 
 impl TextStyle {
-    pub fn color(color: String) -> Self {
+    pub fn color(color: Color) -> Self {
         Self {
             color: Some(color),
             ..Default::default()
@@ -72,40 +76,148 @@ impl Text {
     }
 
     pub fn style(self, style: TextStyle) -> Self {
-        Text(self.0, style)
+        let Text(text, existing) = self;
+        Text(text, existing | style)
     }
 }
 
-impl ToBBCode for Text {
-    fn to_bbcode(self) -> String {
-        let mut text = self.0;
+/// Lets any string-like value be turned into styled `Text` and chained with
+/// `.bold()`/`.italic()`/`.size(n)`/`.color(c)`, mirroring how `Text::style`
+/// composes `TextStyle`s with `|`.
+pub trait TextExt: Sized {
+    fn into_text(self) -> Text;
 
-        if let Some(color) = self.1.color {
-            text = format!("[color={color:}]{text:}[/color]");
-        }
+    fn bold(self) -> Text {
+        self.into_text().style(TextStyle::bold())
+    }
 
-        if let Some(size) = self.1.size {
-            text = format!("[size={size:}]{text:}[/size]");
-        }
+    fn italic(self) -> Text {
+        self.into_text().style(TextStyle::italic())
+    }
 
-        if self.1.bold {
-            text = format!("[b]{text:}[/b]");
-        }
+    fn size(self, size: usize) -> Text {
+        self.into_text().style(TextStyle::size(size))
+    }
 
-        if self.1.italic {
-            text = format!("[i]{text:}[/i]");
-        }
+    fn color(self, color: Color) -> Text {
+        self.into_text().style(TextStyle::color(color))
+    }
+}
+
+impl TextExt for Text {
+    fn into_text(self) -> Text {
+        self
+    }
+}
+
+impl TextExt for &str {
+    fn into_text(self) -> Text {
+        Text::new(self)
+    }
+}
+
+impl TextExt for String {
+    fn into_text(self) -> Text {
+        Text::new(self)
+    }
+}
+
+/// A sequence of independently-styled `Text` spans, built up with `+`.
+#[derive(Default)]
+pub struct Spans(Vec<Text>);
+
+impl<T: TextExt> std::ops::Add<T> for Text {
+    type Output = Spans;
+
+    fn add(self, rhs: T) -> Spans {
+        Spans(vec![self, rhs.into_text()])
+    }
+}
+
+impl<T: TextExt> std::ops::Add<T> for Spans {
+    type Output = Spans;
+
+    fn add(mut self, rhs: T) -> Spans {
+        self.0.push(rhs.into_text());
+        self
+    }
+}
+
+impl<B: Backend> Render<B> for Spans {
+    fn render(self, backend: &B, loader: &Loader) -> Result<String, RenderError> {
+        self.0
+            .into_iter()
+            .map(|span| span.render(backend, loader))
+            .collect()
+    }
+}
 
-        return text;
+impl<B: Backend> Render<B> for Text {
+    fn render(self, backend: &B, _loader: &Loader) -> Result<String, RenderError> {
+        let Text(raw, style) = self;
+
+        // Each wrap's close must only be asked for *after* everything it
+        // encloses has been fully built (including those inner closes) —
+        // not eagerly, up front. A stateful backend (e.g. Ansi) tracks an
+        // open-attribute stack, and calling close_bold() before color has
+        // even been opened would pop the wrong thing.
+        let with_color = |text: String| -> String {
+            if let Some(color) = &style.color {
+                format!(
+                    "{}{}{}",
+                    backend.color(true, color),
+                    text,
+                    backend.color(false, color)
+                )
+            } else {
+                text
+            }
+        };
+
+        let with_size = |text: String| -> String {
+            if let Some(size) = style.size {
+                format!(
+                    "{}{}{}",
+                    backend.size(true, size),
+                    with_color(text),
+                    backend.size(false, size)
+                )
+            } else {
+                with_color(text)
+            }
+        };
+
+        let with_bold = |text: String| -> String {
+            if style.bold {
+                format!("{}{}{}", backend.open_bold(), with_size(text), backend.close_bold())
+            } else {
+                with_size(text)
+            }
+        };
+
+        let with_italic = |text: String| -> String {
+            if style.italic {
+                format!(
+                    "{}{}{}",
+                    backend.open_italic(),
+                    with_bold(text),
+                    backend.close_italic()
+                )
+            } else {
+                with_bold(text)
+            }
+        };
+
+        Ok(with_italic(raw))
     }
 }
 
 #[derive(Debug, Default, Deserialize, Serialize)]
 pub struct Number(f32, #[serde(skip)] TextStyle);
 
-impl ToBBCode for Number {
-    fn to_bbcode(self) -> String {
-        Text(self.0.to_string(), self.1).to_bbcode()
+impl<B: Backend> Render<B> for Number {
+    fn render(self, backend: &B, loader: &Loader) -> Result<String, RenderError> {
+        Text(self.0.to_string(), self.1).render(backend, loader)
     }
 }
 
@@ -114,8 +226,8 @@ pub struct KeyValue(Text, Text);
 impl KeyValue {
     pub fn new<T: ToString, U: ToString>(key: T, value: U) -> Self {
         Self(
-            Text::new(key.to_string() + ":"),
-            Text::new(" ".to_string() + &value.to_string()),
+            (key.to_string() + ":").into_text(),
+            (" ".to_string() + &value.to_string()).into_text(),
         )
     }
 
@@ -132,9 +244,9 @@ impl KeyValue {
     }
 }
 
-impl ToBBCode for KeyValue {
-    fn to_bbcode(self) -> String {
-        format!("{}{}", self.0.to_bbcode(), self.1.to_bbcode())
+impl<B: Backend> Render<B> for KeyValue {
+    fn render(self, backend: &B, loader: &Loader) -> Result<String, RenderError> {
+        (self.0 + self.1).render(backend, loader)
     }
 }
 
@@ -147,19 +259,17 @@ impl<T> FromIterator<T> for List<T> {
     }
 }
 
-impl<T: ToBBCode> ToBBCode for List<T> {
-    fn to_bbcode(self) -> String {
-        let mut out: String = Default::default();
-
-        let mut iter = self.0.into_iter().map(ToBBCode::to_bbcode);
-        let count = iter.len() - 1;
-        for _ in 0..count {
-            out += &iter.next().unwrap();
-            out += "\n";
-        }
-        out += &iter.next().unwrap();
-
-        out
+impl<T, B: Backend> Render<B> for List<T>
+where
+    T: Render<B>,
+{
+    fn render(self, backend: &B, loader: &Loader) -> Result<String, RenderError> {
+        let items = self
+            .0
+            .into_iter()
+            .map(|item| item.render(backend, loader))
+            .collect::<Result<_, _>>()?;
+        Ok(backend.join_list(items, "\n"))
     }
 }
 
@@ -172,20 +282,17 @@ impl<T> FromIterator<T> for DoubleList<T> {
     }
 }
 
-impl<T: ToBBCode> ToBBCode for DoubleList<T> {
-    fn to_bbcode(self) -> String {
-        let mut out: String = Default::default();
-
-        let mut iter = self.0.into_iter().map(ToBBCode::to_bbcode);
-        let count = iter.len() - 1;
-        for _ in 0..count {
-            out += &iter.next().unwrap();
-            out += "\n";
-            out += "\n";
-        }
-        out += &iter.next().unwrap();
-
-        out
+impl<T, B: Backend> Render<B> for DoubleList<T>
+where
+    T: Render<B>,
+{
+    fn render(self, backend: &B, loader: &Loader) -> Result<String, RenderError> {
+        let items = self
+            .0
+            .into_iter()
+            .map(|item| item.render(backend, loader))
+            .collect::<Result<_, _>>()?;
+        Ok(backend.join_list(items, "\n\n"))
     }
 }
 
@@ -198,18 +305,21 @@ impl Image {
     }
 }
 
-impl ToBBCode for Image {
-    fn to_bbcode(self) -> String {
-        format!("[img]{}[/img]", self.0)
+impl<B: Backend> Render<B> for Image {
+    fn render(self, backend: &B, _loader: &Loader) -> Result<String, RenderError> {
+        Ok(backend.image(&self.0))
     }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 struct Spoiler<T>(T);
 
-impl<T: ToBBCode> ToBBCode for Spoiler<T> {
-    fn to_bbcode(self) -> String {
-        format!("[spoiler]{}[/spoiler]", self.0.to_bbcode())
+impl<T, B: Backend> Render<B> for Spoiler<T>
+where
+    T: Render<B>,
+{
+    fn render(self, backend: &B, loader: &Loader) -> Result<String, RenderError> {
+        Ok(backend.spoiler(self.0.render(backend, loader)?))
     }
 }
 
@@ -222,35 +332,42 @@ struct Enemy {
     description: Include<String>,
 }
 
-impl ToBBCode for Enemy {
-    fn to_bbcode(self) -> String {
-        format!(
-            "{}\n\n{}\n\n{}\n\n{}",
-            Text::new(self.name)
-                .style(TextStyle::size(120) | TextStyle::bold() | TextStyle::italic())
-                .to_bbcode(),
-            Image::new(self.image).to_bbcode(),
-            [
-                KeyValue::new("Health", self.health).style_key(TextStyle::bold()),
-                KeyValue::new("Points", self.points).style_key(TextStyle::bold())
-            ]
-            .into_iter()
-            .collect::<List<_>>()
-            .to_bbcode(),
-            self.description.to_bbcode()
-        )
+impl<B: Backend> Render<B> for Enemy {
+    fn render(self, backend: &B, loader: &Loader) -> Result<String, RenderError> {
+        let title = self.name.size(120).bold().italic().render(backend, loader)?;
+        let image = Image::new(self.image).render(backend, loader)?;
+        let stats = [
+            KeyValue::new("Health", self.health).style_key(TextStyle::bold()),
+            KeyValue::new("Points", self.points).style_key(TextStyle::bold()),
+        ]
+        .into_iter()
+        .collect::<List<_>>()
+        .render(backend, loader)?;
+        let description = self.description.render(backend, loader)?;
+
+        Block::new(Direction::Vertical)
+            .border(Border::Light)
+            .padding(Sides::uniform(1))
+            .children(vec![
+                Child::new(AxisSize::Auto, Node::Text(title)),
+                Child::new(AxisSize::Auto, Node::Text(image)),
+                Child::new(AxisSize::Auto, Node::Text(stats)),
+                Child::new(AxisSize::Auto, Node::Text(description)),
+            ])
+            .render(backend, loader)
     }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 struct Include<T>(String, #[serde(skip)] PhantomData<T>);
 
-impl<T: for<'de> Deserialize<'de> + ToBBCode> ToBBCode for Include<T> {
-    fn to_bbcode(self) -> String {
-        println!("Loading {}", self.0);
-        let s = std::fs::read_to_string(self.0).unwrap();
-        let value: T = ron::from_str(&s).unwrap();
-        value.to_bbcode()
+impl<T, B> Render<B> for Include<T>
+where
+    T: for<'de> Deserialize<'de> + Render<B>,
+    B: Backend,
+{
+    fn render(self, backend: &B, loader: &Loader) -> Result<String, RenderError> {
+        loader.load::<T, B>(&self.0, backend)
     }
 }
 
@@ -263,9 +380,9 @@ impl<T> Include<T> {
 #[derive(Debug, Deserialize, Serialize)]
 struct Enemies(DoubleList<Include<List<Enemy>>>);
 
-impl ToBBCode for Enemies {
-    fn to_bbcode(self) -> String {
-        self.0.to_bbcode()
+impl<B: Backend> Render<B> for Enemies {
+    fn render(self, backend: &B, loader: &Loader) -> Result<String, RenderError> {
+        self.0.render(backend, loader)
     }
 }
 
@@ -274,13 +391,123 @@ struct TaromaruST {
     enemies: Include<Enemies>,
 }
 
-impl ToBBCode for TaromaruST {
-    fn to_bbcode(self) -> String {
-        self.enemies.to_bbcode()
+impl<B: Backend> Render<B> for TaromaruST {
+    fn render(self, backend: &B, loader: &Loader) -> Result<String, RenderError> {
+        self.enemies.render(backend, loader)
+    }
+}
+
+/// A double-bordered two-column card (fixed-width sidebar, a `Grow`n,
+/// heavy-bordered main area nested inside it) used to show the layout subsystem and every
+/// backend off together. Built fresh per call since `Block` is consumed by
+/// `render`.
+fn demo_card() -> Block {
+    Block::new(Direction::Horizontal)
+        .border(Border::Double)
+        .margin(Sides::uniform(1))
+        .padding(Sides::uniform(1))
+        .children(vec![
+            Child::new(AxisSize::Fixed(12), Node::Text("Sidebar".to_string())),
+            Child::new(
+                AxisSize::Grow,
+                Node::Block(
+                    Block::new(Direction::Vertical)
+                        .border(Border::Heavy)
+                        .children(vec![
+                            Child::new(AxisSize::Auto, Node::Text("Right top".to_string())),
+                            Child::new(AxisSize::Auto, Node::Text("Right bottom".to_string())),
+                        ]),
+                ),
+            ),
+        ])
+}
+
+/// Renders `demo_card` through every backend and a `Spoiler` through HTML, so the "one
+/// document, many outputs" premise of the `Render`/`Backend` split (and the
+/// layout/spoiler machinery built on top of it) is actually exercised.
+fn print_multi_backend_demo(loader: &Loader) {
+    for (name, rendered) in [
+        ("BBCode", demo_card().render(&Bbcode, loader)),
+        ("HTML", demo_card().render(&Html, loader)),
+        ("Plain", demo_card().render(&Plain, loader)),
+    ] {
+        match rendered {
+            Ok(text) => println!("-- {name} --\n{text}\n"),
+            Err(err) => eprintln!("-- {name} failed: {err}"),
+        }
+    }
+
+    match Spoiler("a hidden detail".to_string()).render(&Html, loader) {
+        Ok(text) => println!("-- spoiler (HTML) --\n{text}"),
+        Err(err) => eprintln!("spoiler failed: {err}"),
+    }
+
+    #[cfg(feature = "ansi")]
+    match demo_card().render(&Ansi::new(), loader) {
+        Ok(text) => println!("-- ANSI --\n{text}"),
+        Err(err) => eprintln!("-- ANSI failed: {err}"),
     }
 }
 
 fn main() {
-    let st = Include::<TaromaruST>::new("data/taromaru-st.ron").to_bbcode();
-    println!("BBCode:\n{}", st);
+    let loader = Loader::new();
+    match Include::<TaromaruST>::new("data/taromaru-st.ron").render(&Bbcode, &loader) {
+        Ok(st) => println!("BBCode:\n{}", st),
+        Err(err) => eprintln!("Failed to render taromaru-st.ron: {err}"),
+    }
+
+    print_multi_backend_demo(&loader);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn styled_hi() -> Text {
+        "hi".to_string().bold().color(Color::Rgb(255, 0, 0))
+    }
+
+    #[test]
+    fn text_renders_per_backend() {
+        let loader = Loader::new();
+        assert_eq!(
+            styled_hi().render(&Bbcode, &loader).unwrap(),
+            "[b][color=#ff0000]hi[/color][/b]"
+        );
+        assert_eq!(
+            styled_hi().render(&Html, &loader).unwrap(),
+            "<b><span style=\"color:#ff0000\">hi</span></b>"
+        );
+        assert_eq!(styled_hi().render(&Plain, &loader).unwrap(), "hi");
+    }
+
+    #[test]
+    fn spoiler_renders_per_backend() {
+        let loader = Loader::new();
+        assert_eq!(
+            Spoiler("secret".to_string()).render(&Bbcode, &loader).unwrap(),
+            "[spoiler]secret[/spoiler]"
+        );
+        assert_eq!(
+            Spoiler("secret".to_string()).render(&Html, &loader).unwrap(),
+            "<details><summary>Spoiler</summary>secret</details>"
+        );
+        assert_eq!(Spoiler("secret".to_string()).render(&Plain, &loader).unwrap(), "secret");
+    }
+
+    /// `Bbcode` never overrides `supports_box_drawing`, so a bordered `Block`
+    /// falls back to `quote`; `Plain` does override it, so the same `Block`
+    /// draws real box-drawing characters instead.
+    #[test]
+    fn block_renders_fallback_and_box_drawing_per_backend() {
+        let loader = Loader::new();
+        let block = || {
+            Block::new(Direction::Vertical)
+                .border(Border::Light)
+                .children(vec![Child::new(AxisSize::Auto, Node::Text("hi".to_string()))])
+        };
+
+        assert_eq!(block().render(&Bbcode, &loader).unwrap(), "[quote]hi[/quote]");
+        assert_eq!(block().render(&Plain, &loader).unwrap(), "┌──┐\n│hi│\n└──┘");
+    }
 }
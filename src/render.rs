@@ -0,0 +1,50 @@
+/// A target format that `Render` implementors serialize into.
+///
+/// Each method is a hook for one piece of markup: text styling, structural
+/// elements, and the join behaviour of list-like containers. Concrete
+/// backends (see the `backend` module) provide the actual syntax for their
+/// output format.
+pub trait Backend: 'static {
+    fn open_bold(&self) -> String;
+    fn close_bold(&self) -> String;
+
+    fn open_italic(&self) -> String;
+    fn close_italic(&self) -> String;
+
+    fn color(&self, open: bool, value: &crate::color::Color) -> String;
+    fn size(&self, open: bool, value: usize) -> String;
+
+    fn image(&self, url: &str) -> String;
+    fn spoiler(&self, body: String) -> String;
+
+    fn join_list(&self, items: Vec<String>, separator: &str) -> String;
+
+    /// Whether this backend can draw real bordered boxes (see the `layout`
+    /// module). Backends without a grid of characters to draw into (e.g.
+    /// BBCode) fall back to `quote`-nested markup instead.
+    fn supports_box_drawing(&self) -> bool {
+        false
+    }
+
+    /// Structural fallback for a `layout::Block` when `supports_box_drawing`
+    /// is `false`: wrap `body` the way this backend nests a blockquote.
+    fn quote(&self, body: String) -> String {
+        body
+    }
+}
+
+/// Renders `Self` into a `String` for a given `Backend`.
+///
+/// This supersedes the old `ToBBCode` trait: instead of every type hardcoding
+/// BBCode syntax, it asks the backend how to realize styling and structure.
+/// The `loader` is threaded through so `Include` (and anything containing
+/// one) can resolve, cache, and cycle-check the files it pulls in.
+pub trait Render<B: Backend> {
+    fn render(self, backend: &B, loader: &crate::loader::Loader) -> Result<String, crate::loader::RenderError>;
+}
+
+impl<B: Backend> Render<B> for String {
+    fn render(self, _backend: &B, _loader: &crate::loader::Loader) -> Result<String, crate::loader::RenderError> {
+        Ok(self)
+    }
+}